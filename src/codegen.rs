@@ -0,0 +1,187 @@
+//! Build-script code generation for large tables.
+//!
+//! The `phf_map!`/`phf_ordered_map!` macros run the CHD generator inside
+//! the compiler via `const fn`, which the crate docs already admit is
+//! "several orders of magnitude slower than `phf`" - fine for a few hundred
+//! entries, impractical for a keyword table with thousands. This module
+//! runs the same displacement search as an ordinary (non-const) function
+//! from a `build.rs`, and emits Rust source that constructs a
+//! [`crate::OrderedMap`] directly. There is no builder for
+//! [`crate::OrderedSet`] (or for the unordered [`crate::Map`]/[`crate::Set`]);
+//! [`Map::build`] only ever writes an `OrderedMap` static.
+//!
+//! The generated source and the const-macro output are interchangeable:
+//! both search the same sequence of `SipHasher13` seeds and use the same
+//! [`crate::displace`] formula, so it doesn't matter to a caller whether a
+//! given static was built by `phf_ordered_map!` or written out here.
+//!
+//! Requires the `codegen` feature (which pulls in `std`, since writing
+//! generated source and running this from a `build.rs` both need it).
+
+extern crate std;
+
+use std::borrow::ToOwned;
+use std::cmp::Reverse;
+use std::fmt::{self, Debug, Write};
+use std::string::String;
+use std::{vec, vec::Vec};
+
+use crate::keys::PhfKeyProxy;
+use crate::{displace, HashValue, Hasher};
+use crate::rand::Rng;
+
+/// Builds the source for a `static` [`crate::OrderedMap`].
+///
+/// Mirrors `phf_codegen::Map`: call [`Map::entry`] for each key (an actual
+/// Rust value, used to run the displacement search and printed back out via
+/// `Debug`) with its value already rendered as a source snippet, then
+/// [`Map::build`] to write the `static` item.
+pub struct Map<K> {
+    entries: Vec<(K, String)>,
+}
+
+impl<K> Map<K> {
+    pub fn new() -> Self {
+        Map {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn entry(&mut self, key: K, value_expr: &str) -> &mut Self {
+        self.entries.push((key, value_expr.to_owned()));
+        self
+    }
+}
+
+impl<K> Default for Map<K> {
+    fn default() -> Self {
+        Map::new()
+    }
+}
+
+impl<K: PhfKeyProxy<K> + Debug> Map<K> {
+    /// Writes `static NAME: cphf::OrderedMap<key_ty, value_ty> = ...;` to
+    /// `w`.
+    pub fn build<W: Write>(
+        &self,
+        name: &str,
+        key_ty: &str,
+        value_ty: &str,
+        w: &mut W,
+    ) -> fmt::Result {
+        let bucket_len = self.entries.len() / 5 + 1;
+
+        let mut seed_idx = 0u64;
+        let (seed, disps, idxs) = loop {
+            assert!(
+                seed_idx < 1_000_000,
+                "cphf::codegen::Map::build: failed to find a perfect hash after 1,000,000 seeds"
+            );
+            let seed = (
+                seed_idx,
+                seed_idx.wrapping_mul(0x9E3779B97F4A7C15).wrapping_add(1),
+            );
+            let hashes: Vec<HashValue> = self
+                .entries
+                .iter()
+                .map(|(key, _)| {
+                    let mut state = Hasher::new_with_keys(seed.0, seed.1);
+                    K::pfh_hash(key, &mut state);
+                    HashValue::finalize(state)
+                })
+                .collect();
+            match generate(&hashes, bucket_len) {
+                Some((disps, idxs)) => break (seed, disps, idxs),
+                None => seed_idx += 1,
+            }
+        };
+
+        writeln!(
+            w,
+            "static {name}: cphf::OrderedMap<{key_ty}, {value_ty}> = cphf::OrderedMap {{"
+        )?;
+        writeln!(w, "    key: ({}, {}),", seed.0, seed.1)?;
+        write!(w, "    disps: &[")?;
+        for (d1, d2) in &disps {
+            write!(w, "({d1}, {d2}), ")?;
+        }
+        writeln!(w, "],")?;
+        write!(w, "    idxs: &[")?;
+        for idx in &idxs {
+            write!(w, "{idx}, ")?;
+        }
+        writeln!(w, "],")?;
+        writeln!(w, "    entries: &[")?;
+        for (key, value_expr) in &self.entries {
+            writeln!(w, "        ({key:?}, {value_expr}),")?;
+        }
+        writeln!(w, "    ],")?;
+        writeln!(w, "}};")
+    }
+}
+
+/// How many `(d1, d2)` candidates a single bucket may try before giving up
+/// on the current seed - mirrors [`crate::Generator`]'s own bound.
+const MAX_DISPLACEMENT_ATTEMPTS: u32 = 1 << 16;
+
+/// A bucket's chosen displacement pairs alongside the final slot -> entry
+/// index assignment, as returned by [`generate`].
+type Displacements = (Vec<(u32, u32)>, Vec<u32>);
+
+/// The non-const twin of [`crate::Generator`]: same bucket-by-`g`,
+/// largest-first, search-for-a-displacement-pair algorithm, but backed by
+/// `Vec` instead of fixed-size const-generic arrays, since the entry count
+/// here is only known at `build.rs` runtime. Returns `None` if some bucket
+/// exhausts [`MAX_DISPLACEMENT_ATTEMPTS`], meaning the caller should rehash
+/// with a different seed and try again.
+fn generate(hashes: &[HashValue], bucket_len: usize) -> Option<Displacements> {
+    let len = hashes.len();
+
+    let mut buckets: Vec<Vec<usize>> = (0..bucket_len).map(|_| Vec::new()).collect();
+    for (i, hash) in hashes.iter().enumerate() {
+        buckets[(hash.g as usize) % bucket_len].push(i);
+    }
+
+    let mut order: Vec<usize> = (0..bucket_len).collect();
+    order.sort_unstable_by_key(|&bucket| Reverse(buckets[bucket].len()));
+
+    let mut map = vec![u32::MAX; len];
+    let mut disps = vec![(0u32, 0u32); bucket_len];
+
+    for bucket in order {
+        if buckets[bucket].is_empty() {
+            continue;
+        }
+
+        let mut rng = Rng::new(bucket as u64 + 1);
+        let mut attempt = 0;
+        loop {
+            if attempt >= MAX_DISPLACEMENT_ATTEMPTS {
+                return None;
+            }
+            attempt += 1;
+
+            let d1 = rng.next_u32();
+            let d2 = rng.next_u32();
+
+            let mut candidate = map.clone();
+            let mut collided = false;
+            for &i in &buckets[bucket] {
+                let slot = (displace(hashes[i].f1, hashes[i].f2, d1, d2) as usize) % len;
+                if candidate[slot] != u32::MAX {
+                    collided = true;
+                    break;
+                }
+                candidate[slot] = i as u32;
+            }
+
+            if !collided {
+                map = candidate;
+                disps[bucket] = (d1, d2);
+                break;
+            }
+        }
+    }
+
+    Some((disps, map))
+}
@@ -0,0 +1,315 @@
+//! The container types produced by this crate's macros.
+//!
+//! [`OrderedMap`]/[`OrderedSet`] preserve insertion order: each entry is
+//! stored in the order it was declared, and an `idxs` table (slot -> entry
+//! index) sits in front of it so that iteration and [`OrderedSet::index`]
+//! see entries in declaration order while lookups still run in constant
+//! time.
+//!
+//! [`Map`]/[`Set`] drop that indirection: entries are stored directly in
+//! perfect-hash-slot order, so a lookup costs one `disps` read, one
+//! [`displace`] and one `entries` read - no second array hop. Use these
+//! when iteration/declaration order doesn't matter (keyword tables, static
+//! lookup sets); use the `Ordered*` variants when it does.
+
+use crate::keys::PhfKeyProxy;
+use crate::{displace, HashValue, Hasher};
+
+/// An unordered static map built by [`crate::phf_map`].
+///
+/// Entries live directly at their perfect-hash slot, so `get` is one
+/// `disps` read, one [`displace`] and one `entries` read - no indirection
+/// through an index table. The cost is that `entries` is in whatever order
+/// the perfect hash function assigned, not declaration order; use
+/// [`OrderedMap`] if you need the latter.
+pub struct Map<K: 'static, V: 'static> {
+    /// The `SipHasher13` keys this table was built with.
+    #[doc(hidden)]
+    pub key: (u64, u64),
+    #[doc(hidden)]
+    pub disps: &'static [(u32, u32)],
+    #[doc(hidden)]
+    pub entries: &'static [(K, V)],
+}
+
+impl<K: 'static, V: 'static> Map<K, V> {
+    /// Hashes `key` with this table's `SipHasher13` keys.
+    pub fn hash<PK: ?Sized>(&self, key: &PK) -> HashValue
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        let mut state = Hasher::new_with_keys(self.key.0, self.key.1);
+        K::pfh_hash(key, &mut state);
+        HashValue::finalize(state)
+    }
+
+    /// Returns the matched entry's stored key alongside its value, useful
+    /// for recovering the canonical `'static` form of a key a caller only
+    /// has a borrowed copy of.
+    pub fn get_entry<PK: ?Sized>(&self, key: &PK) -> Option<(&K, &V)>
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        if self.entries.is_empty() {
+            return None;
+        }
+        let hash = self.hash(key);
+        let (d1, d2) = self.disps[(hash.g as usize) % self.disps.len()];
+        let entry = &self.entries[(displace(hash.f1, hash.f2, d1, d2) as usize) % self.entries.len()];
+        if entry.0.pfh_eq(key) {
+            Some((&entry.0, &entry.1))
+        } else {
+            None
+        }
+    }
+
+    /// Alias of [`Map::get_entry`] matching `std`'s
+    /// `HashMap::get_key_value` naming.
+    pub fn get_key_value<PK: ?Sized>(&self, key: &PK) -> Option<(&K, &V)>
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        self.get_entry(key)
+    }
+
+    pub fn get<PK: ?Sized>(&self, key: &PK) -> Option<&V>
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        self.get_entry(key).map(|(_, v)| v)
+    }
+
+    pub fn contains_key<PK: ?Sized>(&self, key: &PK) -> bool
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        self.get(key).is_some()
+    }
+}
+
+/// An unordered static set built by [`crate::phf_set`]. See [`Map`] for why
+/// it's cheaper than [`OrderedSet`] when order doesn't matter.
+pub struct Set<T: 'static> {
+    #[doc(hidden)]
+    pub map: Map<T, ()>,
+}
+
+impl<T: 'static> Set<T> {
+    pub fn contains<PK: ?Sized>(&self, value: &PK) -> bool
+    where
+        T: PhfKeyProxy<PK>,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Returns the interned element equal to `value`, or `None` if it isn't
+    /// in the set.
+    ///
+    /// ```
+    /// use cphf::{phf_set, Set, UncasedStr};
+    ///
+    /// static KEYWORDS: Set<UncasedStr> = phf_set! {UncasedStr; UncasedStr::new("fn")};
+    ///
+    /// // The probed case differs from the declared case; `get` hands back
+    /// // the canonical, declared-case form, not the probe.
+    /// assert_eq!(KEYWORDS.get("FN").map(UncasedStr::as_str), Some("fn"));
+    /// ```
+    pub fn get<PK: ?Sized>(&self, value: &PK) -> Option<&T>
+    where
+        T: PhfKeyProxy<PK>,
+    {
+        self.map.get_entry(value).map(|(key, _)| key)
+    }
+}
+
+/// An order-preserving static map built by [`crate::phf_ordered_map`].
+///
+/// `idxs[slot]` is the index into `entries` (in declaration order) that the
+/// perfect hash function placed at that slot, so `get` costs one extra
+/// array read over [`Map::get`] in exchange for `entries` (and therefore
+/// iteration) staying in declaration order.
+pub struct OrderedMap<K: 'static, V: 'static> {
+    /// The `SipHasher13` keys this table was built with.
+    #[doc(hidden)]
+    pub key: (u64, u64),
+    #[doc(hidden)]
+    pub disps: &'static [(u32, u32)],
+    #[doc(hidden)]
+    pub idxs: &'static [u32],
+    #[doc(hidden)]
+    pub entries: &'static [(K, V)],
+}
+
+impl<K: 'static, V: 'static> OrderedMap<K, V> {
+    /// Hashes `key` with this table's `SipHasher13` keys.
+    ///
+    /// A caller that misses here can reuse the returned [`HashValue`] to
+    /// probe a dynamic fallback table (e.g. a `HashMap` backing an interner)
+    /// without running SipHash a second time.
+    ///
+    /// ```
+    /// use cphf::{phf_ordered_map, OrderedMap};
+    ///
+    /// static NAMES: OrderedMap<&'static str, u32> = phf_ordered_map! {&'static str, u32;
+    ///     "alice" => 0,
+    ///     "bob" => 1,
+    /// };
+    ///
+    /// let hash = NAMES.hash("bob");
+    /// assert_eq!(NAMES.get_by_hash(&hash), Some(&1));
+    /// ```
+    pub fn hash<PK: ?Sized>(&self, key: &PK) -> HashValue
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        let mut state = Hasher::new_with_keys(self.key.0, self.key.1);
+        K::pfh_hash(key, &mut state);
+        HashValue::finalize(state)
+    }
+
+    /// Runs the displacement lookup for a hash already computed by
+    /// [`OrderedMap::hash`], skipping the rehash `get`/`get_index` would do.
+    ///
+    /// This does *not* check that `hash` actually belongs to a key in the
+    /// table: a hash for a key that isn't present still lands on some slot,
+    /// it just isn't the slot you're looking for. Use this only once you've
+    /// otherwise established the key is present (or don't care which entry
+    /// you get back).
+    pub fn get_by_hash(&self, hash: &HashValue) -> Option<&V> {
+        if self.idxs.is_empty() {
+            return None;
+        }
+        let (d1, d2) = self.disps[(hash.g as usize) % self.disps.len()];
+        let idx = self.idxs[(displace(hash.f1, hash.f2, d1, d2) as usize) % self.idxs.len()];
+        Some(&self.entries[idx as usize].1)
+    }
+
+    /// Returns the declaration-order index of `key`, or `None` if it isn't
+    /// in the table.
+    pub fn get_index<PK: ?Sized>(&self, key: &PK) -> Option<usize>
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        if self.idxs.is_empty() {
+            return None;
+        }
+        let hash = self.hash(key);
+        let (d1, d2) = self.disps[(hash.g as usize) % self.disps.len()];
+        let idx = self.idxs[(displace(hash.f1, hash.f2, d1, d2) as usize) % self.idxs.len()];
+        if self.entries[idx as usize].0.pfh_eq(key) {
+            Some(idx as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Alias of [`OrderedMap::get_index`] for call sites that want to make
+    /// it explicit they're indexing by key, not by position.
+    pub fn get_key_index<PK: ?Sized>(&self, key: &PK) -> Option<usize>
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        self.get_index(key)
+    }
+
+    pub fn get<PK: ?Sized>(&self, key: &PK) -> Option<&V>
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        self.get_index(key).map(|idx| &self.entries[idx].1)
+    }
+
+    /// Returns the matched entry's stored key alongside its value, useful
+    /// for recovering the canonical `'static` form of a key a caller only
+    /// has a borrowed copy of.
+    ///
+    /// ```
+    /// use cphf::{phf_ordered_map, OrderedMap, UncasedStr};
+    ///
+    /// static KEYWORDS: OrderedMap<UncasedStr, u32> = phf_ordered_map! {UncasedStr, u32;
+    ///     UncasedStr::new("fn") => 0,
+    /// };
+    ///
+    /// // The probe key's case differs from the stored key's; `get_entry`
+    /// // hands back the canonical, declared-case form, not the probe.
+    /// let (key, value) = KEYWORDS.get_entry("FN").unwrap();
+    /// assert_eq!(key.as_str(), "fn");
+    /// assert_eq!(*value, 0);
+    /// ```
+    pub fn get_entry<PK: ?Sized>(&self, key: &PK) -> Option<(&K, &V)>
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        self.get_index(key)
+            .map(|idx| (&self.entries[idx].0, &self.entries[idx].1))
+    }
+
+    /// Alias of [`OrderedMap::get_entry`] matching `std`'s
+    /// `HashMap::get_key_value` naming.
+    pub fn get_key_value<PK: ?Sized>(&self, key: &PK) -> Option<(&K, &V)>
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        self.get_entry(key)
+    }
+
+    pub fn contains_key<PK: ?Sized>(&self, key: &PK) -> bool
+    where
+        K: PhfKeyProxy<PK>,
+    {
+        self.get_index(key).is_some()
+    }
+}
+
+/// An order-preserving static set built by [`crate::phf_ordered_set`].
+pub struct OrderedSet<T: 'static> {
+    #[doc(hidden)]
+    pub map: OrderedMap<T, ()>,
+}
+
+impl<T: 'static> OrderedSet<T> {
+    pub fn contains<PK: ?Sized>(&self, value: &PK) -> bool
+    where
+        T: PhfKeyProxy<PK>,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Returns the interned element equal to `value`, or `None` if it isn't
+    /// in the set.
+    pub fn get<PK: ?Sized>(&self, value: &PK) -> Option<&T>
+    where
+        T: PhfKeyProxy<PK>,
+    {
+        self.map.get_entry(value).map(|(key, _)| key)
+    }
+
+    /// Returns the declaration-order index of `value`, or `None` if it
+    /// isn't in the set. Useful for interning: turn a key into a stable
+    /// small integer with this, and back into a key with
+    /// [`OrderedSet::index`].
+    pub fn get_index<PK: ?Sized>(&self, value: &PK) -> Option<usize>
+    where
+        T: PhfKeyProxy<PK>,
+    {
+        self.map.get_index(value)
+    }
+
+    /// Returns the element declared at `index`, the inverse of
+    /// [`OrderedSet::get_index`].
+    ///
+    /// ```
+    /// use cphf::{phf_ordered_set, OrderedSet};
+    ///
+    /// static NAMES: OrderedSet<&'static str> = phf_ordered_set! {&'static str;
+    ///     "alice",
+    ///     "bob",
+    /// };
+    ///
+    /// let idx = NAMES.get_index("bob").unwrap();
+    /// assert_eq!(NAMES.index(idx), Some(&"bob"));
+    /// ```
+    pub fn index(&self, index: usize) -> Option<&T> {
+        self.map.entries.get(index).map(|(k, _)| k)
+    }
+}
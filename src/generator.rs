@@ -0,0 +1,130 @@
+//! The CHD ([Hash, Displace](http://cmph.sourceforge.net/papers/esa09.pdf))
+//! perfect hash generator.
+//!
+//! Keys are first hashed into buckets by `g` (one of the three values in
+//! [`crate::HashValue`]); buckets are then placed into the final table,
+//! largest first, by searching for a displacement pair `(d1, d2)` such that
+//! [`crate::displace`]`(f1, f2, d1, d2) % LEN` lands every key in the bucket
+//! on a slot nothing else has claimed yet.
+//!
+//! Everything here runs inside a `const fn`, so there's no `Vec`: buckets
+//! are never materialized as a list of indices, they're re-scanned out of
+//! `hashes` each time a candidate `(d1, d2)` is tried. That's asymptotically
+//! worse than the classic mutable-bucket-list CHD implementation, but it's
+//! the price of doing this at compile time instead of via a build script
+//! (see the `codegen` module for the latter).
+
+use crate::rand::Rng;
+use crate::{displace, HashValue};
+
+/// The final computed state of a table's generation: which bucket picked
+/// which displacement pair, and which original key index ended up in each
+/// slot.
+#[doc(hidden)]
+pub struct BuilderState<const LEN: usize, const BUCKET_LEN: usize> {
+    pub disps: [(u32, u32); BUCKET_LEN],
+    pub map: [u32; LEN],
+}
+
+/// Runs the CHD algorithm over a fixed-size array of pre-hashed keys.
+pub struct Generator<const LEN: usize, const BUCKET_LEN: usize>;
+
+impl<const LEN: usize, const BUCKET_LEN: usize> Generator<LEN, BUCKET_LEN> {
+    /// How many `(d1, d2)` candidates a single bucket may try before giving
+    /// up on the current hash seed. Some seeds just don't admit a placement
+    /// for a given key set, so this has to be bounded or a bad seed spins
+    /// forever instead of being abandoned; see [`Generator::try_build`].
+    const MAX_DISPLACEMENT_ATTEMPTS: u32 = 1 << 16;
+
+    /// Computes a displacement table and slot assignment for `hashes`, or
+    /// `None` if some bucket couldn't find a non-colliding displacement
+    /// within [`Generator::MAX_DISPLACEMENT_ATTEMPTS`] tries - in which case
+    /// the caller should rehash with a different `SipHasher13` seed and try
+    /// again (the macros' `build_table` const fn does this).
+    ///
+    /// `BUCKET_LEN` is chosen by the macros as roughly `LEN / 5 + 1`,
+    /// matching the bucket sizing `phf` uses; larger buckets mean fewer,
+    /// harder-to-place buckets, smaller buckets mean more (cheap) buckets
+    /// to search through.
+    pub const fn try_build(hashes: &[HashValue; LEN]) -> Option<BuilderState<LEN, BUCKET_LEN>> {
+        let mut bucket_len = [0u32; BUCKET_LEN];
+        let mut i = 0;
+        while i < LEN {
+            let bucket = (hashes[i].g as usize) % BUCKET_LEN;
+            bucket_len[bucket] += 1;
+            i += 1;
+        }
+
+        // Buckets are placed largest-first: a bucket with many keys is much
+        // harder to place once the table has filled up, so it should get
+        // first pick of slots.
+        let mut bucket_order = [0usize; BUCKET_LEN];
+        i = 0;
+        while i < BUCKET_LEN {
+            bucket_order[i] = i;
+            i += 1;
+        }
+        let mut a = 0;
+        while a < BUCKET_LEN {
+            let mut largest = a;
+            let mut b = a + 1;
+            while b < BUCKET_LEN {
+                if bucket_len[bucket_order[b]] > bucket_len[bucket_order[largest]] {
+                    largest = b;
+                }
+                b += 1;
+            }
+            let tmp = bucket_order[a];
+            bucket_order[a] = bucket_order[largest];
+            bucket_order[largest] = tmp;
+            a += 1;
+        }
+
+        let mut map = [u32::MAX; LEN];
+        let mut disps = [(0u32, 0u32); BUCKET_LEN];
+
+        let mut order_idx = 0;
+        while order_idx < BUCKET_LEN {
+            let bucket = bucket_order[order_idx];
+            order_idx += 1;
+            if bucket_len[bucket] == 0 {
+                continue;
+            }
+
+            let mut rng = Rng::new(bucket as u64 + 1);
+            let mut attempt = 0;
+            loop {
+                if attempt >= Self::MAX_DISPLACEMENT_ATTEMPTS {
+                    return None;
+                }
+                attempt += 1;
+
+                let d1 = rng.next_u32();
+                let d2 = rng.next_u32();
+
+                let mut candidate = map;
+                let mut collided = false;
+                let mut i = 0;
+                while i < LEN {
+                    if (hashes[i].g as usize) % BUCKET_LEN == bucket {
+                        let slot = (displace(hashes[i].f1, hashes[i].f2, d1, d2) as usize) % LEN;
+                        if candidate[slot] != u32::MAX {
+                            collided = true;
+                            break;
+                        }
+                        candidate[slot] = i as u32;
+                    }
+                    i += 1;
+                }
+
+                if !collided {
+                    map = candidate;
+                    disps[bucket] = (d1, d2);
+                    break;
+                }
+            }
+        }
+
+        Some(BuilderState { disps, map })
+    }
+}
@@ -0,0 +1,269 @@
+//! Const-friendly key hashing.
+//!
+//! `const fn` can't call trait methods generically, so every hashable key
+//! type is split into two halves:
+//!
+//! - [`PhfKey`] links a key type to a marker type via `ConstKey`.
+//! - The marker type implements `pfh_hash`/`pfh_eq` as plain inherent
+//!   `const fn`s, which the macros call directly while building a table.
+//!
+//! [`PhfKeyProxy`] is the ordinary (non-const) trait that container lookups
+//! use at runtime, so a caller can probe e.g. an `OrderedMap<&'static str, V>`
+//! with any `PK: Borrow<str>` without needing an owned `&'static str`.
+//!
+//! Most callers never touch this module directly; see the crate-level docs
+//! for a worked example of implementing it for a custom key type.
+
+use core::borrow::Borrow;
+
+use crate::Hasher;
+
+/// Links a key type to its [`ConstKey`] marker.
+pub trait PhfKey {
+    type ConstKey: ConstKey<PhfKey = Self>;
+}
+
+/// The `const fn` half of a key implementation. See the module docs for why
+/// this exists instead of a single trait with const methods.
+pub trait ConstKey {
+    type PhfKey: PhfKey<ConstKey = Self>;
+}
+
+/// Runtime lookup trait: lets a container be probed with any borrowed form
+/// of its key (e.g. `&str` against an `OrderedMap<&'static str, V>`).
+pub trait PhfKeyProxy<PK: ?Sized> {
+    fn pfh_hash(pk: &PK, state: &mut Hasher);
+    fn pfh_eq(&self, other: &PK) -> bool;
+}
+
+#[doc(hidden)]
+pub struct IntMarker<T>(core::marker::PhantomData<T>);
+
+macro_rules! impl_prim_key {
+    ($($ty:ty),* $(,)?) => {$(
+        impl PhfKey for $ty {
+            type ConstKey = IntMarker<$ty>;
+        }
+        impl ConstKey for IntMarker<$ty> {
+            type PhfKey = $ty;
+        }
+        impl IntMarker<$ty> {
+            // A fixed endianness (rather than `to_ne_bytes`) so a table
+            // built by `codegen` on one host hashes identically to the
+            // same table re-hashed on a cross-compiled, opposite-endian
+            // target.
+            pub const fn pfh_hash(value: &$ty, state: &mut Hasher) {
+                state.write(&value.to_le_bytes());
+            }
+            pub const fn pfh_eq(lhs: &$ty, rhs: &$ty) -> bool {
+                *lhs == *rhs
+            }
+        }
+        impl<PK: ?Sized + Borrow<$ty>> PhfKeyProxy<PK> for $ty {
+            fn pfh_hash(pk: &PK, state: &mut Hasher) {
+                IntMarker::<$ty>::pfh_hash(pk.borrow(), state);
+            }
+            fn pfh_eq(&self, other: &PK) -> bool {
+                self == other.borrow()
+            }
+        }
+    )*};
+}
+
+impl_prim_key!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[doc(hidden)]
+pub struct BoolMarker;
+
+impl PhfKey for bool {
+    type ConstKey = BoolMarker;
+}
+
+impl ConstKey for BoolMarker {
+    type PhfKey = bool;
+}
+
+impl BoolMarker {
+    pub const fn pfh_hash(value: &bool, state: &mut Hasher) {
+        state.write(&[*value as u8]);
+    }
+
+    pub const fn pfh_eq(lhs: &bool, rhs: &bool) -> bool {
+        *lhs == *rhs
+    }
+}
+
+impl<PK: ?Sized + Borrow<bool>> PhfKeyProxy<PK> for bool {
+    fn pfh_hash(pk: &PK, state: &mut Hasher) {
+        BoolMarker::pfh_hash(pk.borrow(), state);
+    }
+
+    fn pfh_eq(&self, other: &PK) -> bool {
+        self == other.borrow()
+    }
+}
+
+#[doc(hidden)]
+pub struct CharMarker;
+
+impl PhfKey for char {
+    type ConstKey = CharMarker;
+}
+
+impl ConstKey for CharMarker {
+    type PhfKey = char;
+}
+
+impl CharMarker {
+    // See `IntMarker`'s `pfh_hash` for why this is a fixed endianness
+    // rather than `to_ne_bytes`.
+    pub const fn pfh_hash(value: &char, state: &mut Hasher) {
+        state.write(&(*value as u32).to_le_bytes());
+    }
+
+    pub const fn pfh_eq(lhs: &char, rhs: &char) -> bool {
+        *lhs == *rhs
+    }
+}
+
+impl<PK: ?Sized + Borrow<char>> PhfKeyProxy<PK> for char {
+    fn pfh_hash(pk: &PK, state: &mut Hasher) {
+        CharMarker::pfh_hash(pk.borrow(), state);
+    }
+
+    fn pfh_eq(&self, other: &PK) -> bool {
+        self == other.borrow()
+    }
+}
+
+/// Marker for `&'static str` keys.
+#[doc(hidden)]
+pub struct StrMarker;
+
+impl PhfKey for &'static str {
+    type ConstKey = StrMarker;
+}
+
+impl ConstKey for StrMarker {
+    type PhfKey = &'static str;
+}
+
+impl StrMarker {
+    pub const fn pfh_hash(value: &&'static str, state: &mut Hasher) {
+        state.write(value.as_bytes());
+    }
+
+    pub const fn pfh_eq(lhs: &&'static str, rhs: &&'static str) -> bool {
+        const_bytes_eq(lhs.as_bytes(), rhs.as_bytes())
+    }
+}
+
+impl<PK: ?Sized + Borrow<str>> PhfKeyProxy<PK> for &'static str {
+    fn pfh_hash(pk: &PK, state: &mut Hasher) {
+        state.write(pk.borrow().as_bytes());
+    }
+
+    fn pfh_eq(&self, other: &PK) -> bool {
+        *self == other.borrow()
+    }
+}
+
+/// Byte-wise equality usable from a `const fn` (slice `PartialEq` isn't
+/// const on our MSRV).
+pub(crate) const fn const_bytes_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < lhs.len() {
+        if lhs[i] != rhs[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Byte-wise ASCII-case-insensitive equality, the `const fn` twin of
+/// [`const_bytes_eq`] used by [`UncasedStr`].
+// `eq_ignore_ascii_case` isn't `const fn` on our MSRV, so the suggested
+// rewrite isn't available here.
+#[allow(clippy::manual_ignore_case_cmp)]
+const fn const_ascii_ieq(lhs: &[u8], rhs: &[u8]) -> bool {
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < lhs.len() {
+        if lhs[i].to_ascii_lowercase() != rhs[i].to_ascii_lowercase() {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// A string key that hashes and compares after folding ASCII letters to
+/// lowercase, so `"FN"` and `"fn"` land in the same slot. Non-ASCII bytes
+/// are compared as-is (no full Unicode case folding).
+///
+/// ```
+/// use cphf::{phf_ordered_map, OrderedMap, UncasedStr};
+///
+/// static KEYWORDS: OrderedMap<UncasedStr, u32> = phf_ordered_map! {UncasedStr, u32;
+///     UncasedStr::new("fn") => 0,
+///     UncasedStr::new("loop") => 1,
+/// };
+///
+/// assert_eq!(KEYWORDS.get("FN"), Some(&0));
+/// ```
+#[derive(Clone, Copy)]
+pub struct UncasedStr(&'static str);
+
+impl UncasedStr {
+    pub const fn new(s: &'static str) -> Self {
+        UncasedStr(s)
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+#[doc(hidden)]
+pub struct UncasedMarker;
+
+impl PhfKey for UncasedStr {
+    type ConstKey = UncasedMarker;
+}
+
+impl ConstKey for UncasedMarker {
+    type PhfKey = UncasedStr;
+}
+
+impl UncasedMarker {
+    pub const fn pfh_hash(value: &UncasedStr, state: &mut Hasher) {
+        let bytes = value.0.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            state.write(&[bytes[i].to_ascii_lowercase()]);
+            i += 1;
+        }
+    }
+
+    pub const fn pfh_eq(lhs: &UncasedStr, rhs: &UncasedStr) -> bool {
+        const_ascii_ieq(lhs.0.as_bytes(), rhs.0.as_bytes())
+    }
+}
+
+impl<PK: ?Sized + Borrow<str>> PhfKeyProxy<PK> for UncasedStr {
+    fn pfh_hash(pk: &PK, state: &mut Hasher) {
+        for byte in pk.borrow().as_bytes() {
+            state.write(&[byte.to_ascii_lowercase()]);
+        }
+    }
+
+    fn pfh_eq(&self, other: &PK) -> bool {
+        const_ascii_ieq(self.0.as_bytes(), other.borrow().as_bytes())
+    }
+}
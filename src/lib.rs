@@ -144,6 +144,24 @@
 //! };
 //! ```
 //!
+//! ## Code generation
+//!
+//! The `const`-eval generator above is several orders of magnitude slower
+//! than [`phf`](https://github.com/rust-phf/rust-phf/)'s build-script
+//! approach, so tables with thousands of entries are impractical to build
+//! with the macros. The `codegen` feature (which pulls in `std`) adds
+//! [`codegen::Map`], a `phf_codegen`-style builder meant to be driven from a
+//! `build.rs` and to `write!` a `static` item into a generated source file.
+//! Tables it emits use the same `SipHasher13` keys and displacement formula
+//! as the macros, so the two are interchangeable.
+//!
+//! ```ignore
+//! // build.rs
+//! let mut map = cphf::codegen::Map::new();
+//! map.entry("loop", "Keyword::Loop");
+//! map.entry("continue", "Keyword::Continue");
+//! map.build("KEYWORDS", "&'static str", "Keyword", &mut out)?;
+//! ```
 
 #![no_std]
 
@@ -153,6 +171,8 @@ pub use const_siphasher::sip128::SipHasher13 as Hasher;
 #[doc(hidden)]
 pub use sort_const::const_shellsort;
 
+#[cfg(feature = "codegen")]
+pub mod codegen;
 mod containers;
 mod generator;
 mod keys;
@@ -162,7 +182,6 @@ mod rand;
 pub use containers::*;
 pub use generator::Generator;
 pub use keys::*;
-pub use macros::*;
 
 /// The final computed state during map generation
 ///
@@ -173,7 +192,7 @@ pub type BuilderState<const LEN: usize, const BUCKET_LEN: usize> =
 
 /// A hash result broken down into parts for ease of use in displacement
 #[doc(hidden)]
-#[derive(Default)]
+#[derive(Clone, Copy, Default)]
 pub struct HashValue {
     g: u32,
     f1: u32,
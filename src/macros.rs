@@ -0,0 +1,210 @@
+//! The `phf_map!`/`phf_set!`/`phf_ordered_map!`/`phf_ordered_set!` macros.
+//!
+//! All four follow the same shape: collect the entries into a `const`
+//! array, compare every pair of keys (panicking on a duplicate, which is how
+//! the crate-level `compile_fail` doctests work), hash each key, run
+//! [`crate::Generator`] over the hashes, and wire the result into the
+//! matching container type. None
+//! of this is a proc macro - it's all `const fn` evaluated by the ordinary
+//! compiler, which is what keeps this crate from needing a build step.
+
+/// Builds an [`crate::OrderedMap`] with entries stored in declaration
+/// order. See the crate-level docs for a full example.
+#[macro_export]
+macro_rules! phf_ordered_map {
+    ($K:ty, $V:ty; $($key:expr => $value:expr),* $(,)?) => {{
+        const ENTRIES: &[($K, $V)] = &[$(($key, $value)),*];
+        const LEN: usize = ENTRIES.len();
+        const BUCKET_LEN: usize = LEN / 5 + 1;
+
+        const fn check_duplicates() {
+            let mut i = 0;
+            while i < LEN {
+                let mut j = 0;
+                while j < i {
+                    if <<$K as $crate::PhfKey>::ConstKey>::pfh_eq(&ENTRIES[i].0, &ENTRIES[j].0) {
+                        panic!("duplicate key in phf_ordered_map!");
+                    }
+                    j += 1;
+                }
+                i += 1;
+            }
+        }
+
+        const fn hash_with_seed(seed: (u64, u64)) -> [$crate::HashValue; LEN] {
+            let mut hashes = [$crate::HashValue::new(); LEN];
+            let mut i = 0;
+            while i < LEN {
+                let mut state = $crate::Hasher::new_with_keys(seed.0, seed.1);
+                <<$K as $crate::PhfKey>::ConstKey>::pfh_hash(&ENTRIES[i].0, &mut state);
+                hashes[i] = $crate::HashValue::finalize(state);
+                i += 1;
+            }
+            hashes
+        }
+
+        // Not every `SipHasher13` seed admits a placement for a given key
+        // set (see `Generator::try_build`), so try seeds in order and move
+        // on from one that doesn't pan out, rather than hardcoding `(0, 0)`
+        // and spinning forever on it.
+        const fn build_table() -> ($crate::BuilderState<LEN, BUCKET_LEN>, (u64, u64)) {
+            check_duplicates();
+            let mut seed_idx: u64 = 0;
+            loop {
+                if seed_idx >= 1_000_000 {
+                    panic!("phf_ordered_map!: failed to find a perfect hash after 1,000,000 seeds");
+                }
+                let seed = (seed_idx, seed_idx.wrapping_mul(0x9E3779B97F4A7C15).wrapping_add(1));
+                let hashes = hash_with_seed(seed);
+                match $crate::Generator::<LEN, BUCKET_LEN>::try_build(&hashes) {
+                    Some(state) => break (state, seed),
+                    None => seed_idx += 1,
+                }
+            }
+        }
+
+        const BUILT: ($crate::BuilderState<LEN, BUCKET_LEN>, (u64, u64)) = build_table();
+        const DISPS: [(u32, u32); BUCKET_LEN] = BUILT.0.disps;
+        const IDXS: [u32; LEN] = BUILT.0.map;
+
+        $crate::OrderedMap {
+            key: BUILT.1,
+            disps: &DISPS,
+            idxs: &IDXS,
+            entries: ENTRIES,
+        }
+    }};
+}
+
+/// Builds an [`crate::OrderedSet`] with elements stored in declaration
+/// order. See the crate-level docs for a full example.
+#[macro_export]
+macro_rules! phf_ordered_set {
+    ($T:ty; $($value:expr),* $(,)?) => {
+        $crate::OrderedSet {
+            map: $crate::phf_ordered_map!($T, (); $($value => ()),*),
+        }
+    };
+}
+
+/// Builds a [`crate::Map`] with entries stored in perfect-hash-slot order.
+/// Cheaper than [`phf_ordered_map!`] when iteration/declaration order
+/// doesn't matter.
+///
+/// ```
+/// use cphf::{phf_map, phf_set, Map, Set};
+///
+/// static COLORS: Map<&'static str, u32> = phf_map! {&'static str, u32;
+///     "red" => 0xff0000,
+///     "green" => 0x00ff00,
+///     "blue" => 0x0000ff,
+/// };
+///
+/// assert_eq!(COLORS.get("green"), Some(&0x00ff00));
+/// assert_eq!(COLORS.get("purple"), None);
+///
+/// static PRIMES: Set<u32> = phf_set! {u32; 2, 3, 5, 7, 11};
+///
+/// assert!(PRIMES.contains(&7));
+/// assert!(!PRIMES.contains(&9));
+/// ```
+#[macro_export]
+macro_rules! phf_map {
+    ($K:ty, $V:ty; $($key:expr => $value:expr),* $(,)?) => {{
+        const DECLARED: &[($K, $V)] = &[$(($key, $value)),*];
+        const LEN: usize = DECLARED.len();
+        const BUCKET_LEN: usize = LEN / 5 + 1;
+
+        const fn check_duplicates() {
+            let mut i = 0;
+            while i < LEN {
+                let mut j = 0;
+                while j < i {
+                    if <<$K as $crate::PhfKey>::ConstKey>::pfh_eq(&DECLARED[i].0, &DECLARED[j].0) {
+                        panic!("duplicate key in phf_map!");
+                    }
+                    j += 1;
+                }
+                i += 1;
+            }
+        }
+
+        const fn hash_with_seed(seed: (u64, u64)) -> [$crate::HashValue; LEN] {
+            let mut hashes = [$crate::HashValue::new(); LEN];
+            let mut i = 0;
+            while i < LEN {
+                let mut state = $crate::Hasher::new_with_keys(seed.0, seed.1);
+                <<$K as $crate::PhfKey>::ConstKey>::pfh_hash(&DECLARED[i].0, &mut state);
+                hashes[i] = $crate::HashValue::finalize(state);
+                i += 1;
+            }
+            hashes
+        }
+
+        // See `phf_ordered_map!`'s `build_table` for why this retries seeds
+        // instead of hardcoding `(0, 0)`.
+        const fn build_table() -> ($crate::BuilderState<LEN, BUCKET_LEN>, (u64, u64)) {
+            check_duplicates();
+            let mut seed_idx: u64 = 0;
+            loop {
+                if seed_idx >= 1_000_000 {
+                    panic!("phf_map!: failed to find a perfect hash after 1,000,000 seeds");
+                }
+                let seed = (seed_idx, seed_idx.wrapping_mul(0x9E3779B97F4A7C15).wrapping_add(1));
+                let hashes = hash_with_seed(seed);
+                match $crate::Generator::<LEN, BUCKET_LEN>::try_build(&hashes) {
+                    Some(state) => break (state, seed),
+                    None => seed_idx += 1,
+                }
+            }
+        }
+
+        const BUILT: ($crate::BuilderState<LEN, BUCKET_LEN>, (u64, u64)) = build_table();
+        const IDXS: [u32; LEN] = BUILT.0.map;
+        const DISPS: [(u32, u32); BUCKET_LEN] = BUILT.0.disps;
+
+        // Re-order the declared entries into slot order so `Map::get` can
+        // index straight into `entries` without an `idxs` indirection. This
+        // has to work for non-`Copy` keys/values too, and for `LEN == 0`, so
+        // it builds the array via `MaybeUninit` and `ptr::read` out of
+        // `DECLARED` instead of `Copy`-ing through a `[DECLARED[0]; LEN]`
+        // seed value.
+        const ENTRIES: [($K, $V); LEN] = {
+            // SAFETY: an array of `MaybeUninit<T>` is valid in any bit
+            // pattern, including fully uninitialized, regardless of `T`.
+            let mut entries: [::core::mem::MaybeUninit<($K, $V)>; LEN] =
+                unsafe { ::core::mem::MaybeUninit::uninit().assume_init() };
+            let mut slot = 0;
+            while slot < LEN {
+                // SAFETY: `IDXS` is the permutation of `0..LEN` the
+                // generator assigned, so every source index is read exactly
+                // once here, and the duplicated bytes never get dropped
+                // twice since `DECLARED`/`ENTRIES` are both `'static`.
+                entries[slot] = ::core::mem::MaybeUninit::new(unsafe {
+                    ::core::ptr::read(&DECLARED[IDXS[slot] as usize])
+                });
+                slot += 1;
+            }
+            // SAFETY: every slot was written above.
+            unsafe { ::core::mem::transmute_copy(&entries) }
+        };
+
+        $crate::Map {
+            key: BUILT.1,
+            disps: &DISPS,
+            entries: &ENTRIES,
+        }
+    }};
+}
+
+/// Builds a [`crate::Set`] with elements stored in perfect-hash-slot order.
+/// Cheaper than [`phf_ordered_set!`] when iteration/declaration order
+/// doesn't matter.
+#[macro_export]
+macro_rules! phf_set {
+    ($T:ty; $($value:expr),* $(,)?) => {
+        $crate::Set {
+            map: $crate::phf_map!($T, (); $($value => ()),*),
+        }
+    };
+}
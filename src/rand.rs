@@ -0,0 +1,29 @@
+//! A tiny `const fn`-compatible pseudo-random source.
+//!
+//! The CHD generator needs to try a sequence of `(d1, d2)` displacement
+//! candidates per bucket until it finds a pair that doesn't collide with
+//! anything already placed. It doesn't need real randomness (the input keys
+//! are fixed at compile time, so there is nothing to defend against) - it
+//! just needs candidates that don't all land in the same place, which a
+//! xorshift generator is more than good enough for.
+
+/// xorshift64 state, seeded deterministically per bucket so that generation
+/// is reproducible across compiler invocations.
+#[doc(hidden)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub const fn new(seed: u64) -> Self {
+        // xorshift64 is undefined for a zero state, so nudge it off zero.
+        Rng(if seed == 0 { 0x9E37_79B9_7F4A_7C15 } else { seed })
+    }
+
+    pub const fn next_u32(&mut self) -> u32 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        (x >> 32) as u32
+    }
+}